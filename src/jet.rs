@@ -0,0 +1,166 @@
+/// A truncated Taylor expansion `[c_0, c_1, ..., c_{N-1}]` of a function
+/// around a point, i.e. the natural generalization of [`DualNumber`](crate::DualNumber)
+/// (the `epsilon^2 = 0` case) to `epsilon^N = 0`.
+///
+/// Stable Rust can't yet spell `[f64; K + 1]` for a const parameter `K`, so
+/// the const parameter `N` here is the *number of coefficients*; the
+/// classical truncation order is `K = N - 1`. `c[n]` relates to the `n`-th
+/// derivative of the represented function at the expansion point by
+/// `f^(n)(x) = c[n] * n!`.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Jet<const N: usize> {
+    c: [f64; N],
+}
+
+fn factorial(n: usize) -> f64 {
+    (1..=n).fold(1.0, |acc, k| acc * k as f64)
+}
+
+impl<const N: usize> Jet<N> {
+    pub fn new(c: [f64; N]) -> Self {
+        Jet { c }
+    }
+
+    /// A constant: every coefficient past `c_0` is zero.
+    pub fn constant(value: f64) -> Self {
+        let mut c = [0.0; N];
+        c[0] = value;
+        Jet::new(c)
+    }
+
+    /// Seeds `value` as the independent variable: `c_0 = value`, `c_1 = 1`
+    /// (if `N > 1`), and all higher coefficients zero.
+    pub fn variable(value: f64) -> Self {
+        let mut c = [0.0; N];
+        c[0] = value;
+        if N > 1 {
+            c[1] = 1.0;
+        }
+        Jet::new(c)
+    }
+
+    /// The `n`-th derivative of the represented function at the expansion
+    /// point, recovered from the `n`-th Taylor coefficient as `c_n * n!`.
+    pub fn nth_derivative(self, n: usize) -> f64 {
+        self.c[n] * factorial(n)
+    }
+
+    pub fn exp(self) -> Self {
+        let mut out = [0.0; N];
+        out[0] = self.c[0].exp();
+        for k in 1..N {
+            let mut sum = 0.0;
+            for j in 1..=k {
+                sum += j as f64 * self.c[j] * out[k - j];
+            }
+            out[k] = sum / k as f64;
+        }
+        Jet::new(out)
+    }
+
+    pub fn sin(self) -> Self {
+        self.sin_cos().0
+    }
+
+    pub fn cos(self) -> Self {
+        self.sin_cos().1
+    }
+
+    /// Computes `sin` and `cos` together via their coupled recurrences,
+    /// since each needs the other's lower-order coefficients. Prefer this
+    /// over calling `sin()` and `cos()` separately when both are needed, to
+    /// avoid running the O(N^2) recurrence twice.
+    pub fn sin_cos(self) -> (Self, Self) {
+        let mut s = [0.0; N];
+        let mut co = [0.0; N];
+        s[0] = self.c[0].sin();
+        co[0] = self.c[0].cos();
+        for k in 1..N {
+            let mut sin_sum = 0.0;
+            let mut cos_sum = 0.0;
+            for j in 1..=k {
+                sin_sum += j as f64 * self.c[j] * co[k - j];
+                cos_sum += j as f64 * self.c[j] * s[k - j];
+            }
+            s[k] = sin_sum / k as f64;
+            co[k] = -cos_sum / k as f64;
+        }
+        (Jet::new(s), Jet::new(co))
+    }
+}
+
+impl<const N: usize> std::ops::Add for Jet<N> {
+    type Output = Jet<N>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut c = self.c;
+        for (ci, rhs_ci) in c.iter_mut().zip(rhs.c) {
+            *ci += rhs_ci;
+        }
+        Jet::new(c)
+    }
+}
+
+impl<const N: usize> std::ops::Sub for Jet<N> {
+    type Output = Jet<N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut c = self.c;
+        for (ci, rhs_ci) in c.iter_mut().zip(rhs.c) {
+            *ci -= rhs_ci;
+        }
+        Jet::new(c)
+    }
+}
+
+impl<const N: usize> std::ops::Mul for Jet<N> {
+    type Output = Jet<N>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // Truncated Cauchy convolution: out[k] = sum_{i=0..=k} x[i]*y[k-i].
+        let mut out = [0.0; N];
+        for (k, out_k) in out.iter_mut().enumerate() {
+            let mut sum = 0.0;
+            for i in 0..=k {
+                sum += self.c[i] * rhs.c[k - i];
+            }
+            *out_k = sum;
+        }
+        Jet::new(out)
+    }
+}
+
+/// Computes the `n`-th derivative of `f` at `x` from a single evaluation,
+/// by seeding a [`Jet`] with `N` coefficients and reading off `c_n * n!`.
+///
+/// `n` must be strictly less than `N`, the number of coefficients tracked.
+pub fn nth_derivative<const N: usize>(x: f64, n: usize, f: impl Fn(Jet<N>) -> Jet<N>) -> f64 {
+    assert!(n < N, "nth_derivative: n must be < N coefficients tracked");
+    f(Jet::variable(x)).nth_derivative(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_derivative_of_sin() {
+        // f(x) = sin(x), f''(x) = -sin(x)
+        let d2 = nth_derivative::<3>(1.0, 2, |x| x.sin());
+        assert!((d2 - -1.0_f64.sin()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn third_derivative_of_exp() {
+        // f(x) = exp(x), f'''(x) = exp(x)
+        let d3 = nth_derivative::<4>(2.0, 3, |x| x.exp());
+        assert!((d3 - 2.0_f64.exp()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn k_equals_1_matches_dual_number_derivative() {
+        // f(x) = x*x, f'(x) = 2x, which is exactly the N=2 (K=1) case.
+        let d1 = nth_derivative::<2>(3.0, 1, |x| x * x);
+        assert!((d1 - 6.0).abs() < 1e-9);
+    }
+}
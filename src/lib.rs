@@ -1,25 +1,270 @@
+mod jet;
+mod multi_dual;
+
+pub use jet::{nth_derivative, Jet};
+pub use multi_dual::{gradient, MultiDual};
+
+/// Numeric operations required of the backing type of a [`DualNumber`].
+///
+/// Implemented for `f32` and `f64` out of the box. Implementing it for other
+/// types (extended-precision floats, interval arithmetic types, or even
+/// `DualNumber<f64>` itself for second derivatives) lets `DualNumber<T>` be
+/// reused wherever forward-mode differentiation is needed.
+pub trait Scalar:
+    Copy
+    + PartialEq
+    + std::ops::Add<Output = Self>
+    + std::ops::Sub<Output = Self>
+    + std::ops::Mul<Output = Self>
+    + std::ops::Div<Output = Self>
+    + std::ops::Rem<Output = Self>
+    + std::ops::Neg<Output = Self>
+{
+    /// Converts a literal like `0.0` or `2.0` into `Self`. A dedicated method
+    /// rather than `std::convert::From<f64>` because that conversion is
+    /// lossy for `f32`, which the standard library therefore declines to
+    /// implement.
+    fn from_f64(x: f64) -> Self;
+
+    fn exp(self) -> Self;
+    fn exp2(self) -> Self;
+    fn ln(self) -> Self;
+    fn log(self, base: Self) -> Self;
+    fn log2(self) -> Self;
+    fn log10(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn cbrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+    fn powf(self, n: f64) -> Self;
+}
+
+impl Scalar for f32 {
+    fn from_f64(x: f64) -> Self {
+        x as f32
+    }
+
+    fn exp(self) -> Self {
+        f32::exp(self)
+    }
+
+    fn exp2(self) -> Self {
+        f32::exp2(self)
+    }
+
+    fn ln(self) -> Self {
+        f32::ln(self)
+    }
+
+    fn log(self, base: Self) -> Self {
+        f32::log(self, base)
+    }
+
+    fn log2(self) -> Self {
+        f32::log2(self)
+    }
+
+    fn log10(self) -> Self {
+        f32::log10(self)
+    }
+
+    fn sin(self) -> Self {
+        f32::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        f32::cos(self)
+    }
+
+    fn tan(self) -> Self {
+        f32::tan(self)
+    }
+
+    fn asin(self) -> Self {
+        f32::asin(self)
+    }
+
+    fn acos(self) -> Self {
+        f32::acos(self)
+    }
+
+    fn atan(self) -> Self {
+        f32::atan(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+
+    fn cbrt(self) -> Self {
+        f32::cbrt(self)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        f32::hypot(self, other)
+    }
+
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
+
+    fn signum(self) -> Self {
+        f32::signum(self)
+    }
+
+    fn powf(self, n: f64) -> Self {
+        f32::powf(self, n as f32)
+    }
+}
+
+impl Scalar for f64 {
+    fn from_f64(x: f64) -> Self {
+        x
+    }
+
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+
+    fn exp2(self) -> Self {
+        f64::exp2(self)
+    }
+
+    fn ln(self) -> Self {
+        f64::ln(self)
+    }
+
+    fn log(self, base: Self) -> Self {
+        f64::log(self, base)
+    }
+
+    fn log2(self) -> Self {
+        f64::log2(self)
+    }
+
+    fn log10(self) -> Self {
+        f64::log10(self)
+    }
+
+    fn sin(self) -> Self {
+        f64::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        f64::cos(self)
+    }
+
+    fn tan(self) -> Self {
+        f64::tan(self)
+    }
+
+    fn asin(self) -> Self {
+        f64::asin(self)
+    }
+
+    fn acos(self) -> Self {
+        f64::acos(self)
+    }
+
+    fn atan(self) -> Self {
+        f64::atan(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn cbrt(self) -> Self {
+        f64::cbrt(self)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        f64::hypot(self, other)
+    }
+
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+
+    fn signum(self) -> Self {
+        f64::signum(self)
+    }
+
+    fn powf(self, n: f64) -> Self {
+        f64::powf(self, n)
+    }
+}
+
 /// Represents the dual number a+b*epsilon, where epsilon \neq 0 satisfies epsilon^2 = 0.
-#[derive(Debug, Clone, Copy, PartialEq,  PartialOrd)]
-struct DualNumber {
-    a: f64,
-    b: f64
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct DualNumber<T: Scalar = f64> {
+    a: T,
+    b: T,
 }
 
-impl DualNumber {
-    pub fn new(a: f64, b: f64) -> Self {
+impl<T: Scalar> DualNumber<T> {
+    pub fn new(a: T, b: T) -> Self {
         DualNumber { a, b }
     }
 
+    /// Lifts a plain value into a dual number with a zero dual part, i.e. a constant.
+    pub fn from_real(x: T) -> Self {
+        DualNumber::new(x, T::from_f64(0.0))
+    }
+
+    /// The real (non-infinitesimal) part, `a`.
+    pub fn real(self) -> T {
+        self.a
+    }
+
+    /// The dual (infinitesimal) part, `b`.
+    pub fn dual(self) -> T {
+        self.b
+    }
+
     pub fn powi(self, n: i64) -> Self {
-        let mut x = self;
-        for _ in 1..n {
-            x *= self;
+        if n == 0 {
+            return DualNumber::from_real(T::from_f64(1.0));
         }
-        x
+        // i64::MIN has no positive i64 representation, so widen to i128
+        // before taking the magnitude instead of negating n directly; binary
+        // exponentiation also keeps this to O(log n) multiplications, so the
+        // magnitude can safely be as large as i64::MIN's without looping forever.
+        let mut exp = (n as i128).unsigned_abs();
+        let mut base = self;
+        let mut result = DualNumber::from_real(T::from_f64(1.0));
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        if n < 0 {
+            DualNumber::from_real(T::from_f64(1.0)) / result
+        } else {
+            result
+        }
+    }
+
+    /// `self^rhs` where both the base and the exponent may vary, via
+    /// `u^v = exp(v * ln(u))`.
+    pub fn pow(self, rhs: Self) -> Self {
+        let ln_a = self.a.ln();
+        let real = (rhs.a * ln_a).exp();
+        let dual = real * (rhs.b * ln_a + rhs.a * self.b / self.a);
+        DualNumber::new(real, dual)
     }
 
-    pub fn log(self, base: f64) -> Self {
-        DualNumber::new(self.a.log(base), 1.0/self.a * self.b)
+    pub fn log(self, base: T) -> Self {
+        DualNumber::new(self.a.log(base), self.b / (self.a * base.ln()))
     }
 
     pub fn exp(self) -> Self {
@@ -31,80 +276,226 @@ impl DualNumber {
     }
 
     pub fn cos(self) -> Self {
-        DualNumber::new(self.a.cos(), self.a.sin() * self.b)
+        DualNumber::new(self.a.cos(), -self.a.sin() * self.b)
+    }
+
+    pub fn tan(self) -> Self {
+        let c = self.a.cos();
+        DualNumber::new(self.a.tan(), self.b / (c * c))
+    }
+
+    pub fn asin(self) -> Self {
+        let denom = (T::from_f64(1.0) - self.a * self.a).sqrt();
+        DualNumber::new(self.a.asin(), self.b / denom)
+    }
+
+    pub fn acos(self) -> Self {
+        let denom = (T::from_f64(1.0) - self.a * self.a).sqrt();
+        DualNumber::new(self.a.acos(), -self.b / denom)
+    }
+
+    pub fn atan(self) -> Self {
+        let denom = T::from_f64(1.0) + self.a * self.a;
+        DualNumber::new(self.a.atan(), self.b / denom)
+    }
+
+    pub fn sqrt(self) -> Self {
+        let sqrt_a = self.a.sqrt();
+        DualNumber::new(sqrt_a, self.b / (T::from_f64(2.0) * sqrt_a))
+    }
+
+    pub fn cbrt(self) -> Self {
+        let cbrt_a = self.a.cbrt();
+        DualNumber::new(cbrt_a, self.b / (T::from_f64(3.0) * cbrt_a * cbrt_a))
+    }
+
+    pub fn ln(self) -> Self {
+        DualNumber::new(self.a.ln(), self.b / self.a)
+    }
+
+    pub fn log2(self) -> Self {
+        DualNumber::new(self.a.log2(), self.b / (self.a * T::from_f64(2.0).ln()))
+    }
+
+    pub fn log10(self) -> Self {
+        DualNumber::new(self.a.log10(), self.b / (self.a * T::from_f64(10.0).ln()))
+    }
+
+    pub fn exp2(self) -> Self {
+        let exp2_a = self.a.exp2();
+        DualNumber::new(exp2_a, exp2_a * T::from_f64(2.0).ln() * self.b)
+    }
+
+    /// `sqrt(self^2 + rhs^2)`, carrying the derivative of both arguments.
+    pub fn hypot(self, rhs: Self) -> Self {
+        let r = self.a.hypot(rhs.a);
+        DualNumber::new(r, (self.a * self.b + rhs.a * rhs.b) / r)
+    }
+
+    pub fn abs(self) -> Self {
+        DualNumber::new(self.a.abs(), self.a.signum() * self.b)
+    }
+
+    pub fn powf(self, n: f64) -> Self {
+        DualNumber::new(
+            self.a.powf(n),
+            T::from_f64(n) * self.a.powf(n - 1.0) * self.b,
+        )
     }
 }
 
-impl std::ops::MulAssign for DualNumber {
+impl<T: Scalar> std::ops::MulAssign for DualNumber<T> {
     fn mul_assign(&mut self, rhs: Self) {
         *self = *self * rhs;
     }
 }
 
-impl std::ops::AddAssign for DualNumber {
+impl<T: Scalar> std::ops::AddAssign for DualNumber<T> {
     fn add_assign(&mut self, rhs: Self) {
         *self = *self + rhs;
     }
 }
 
-impl std::ops::SubAssign for DualNumber {
+impl<T: Scalar> std::ops::SubAssign for DualNumber<T> {
     fn sub_assign(&mut self, rhs: Self) {
         *self = *self - rhs;
     }
 }
 
-impl std::ops::DivAssign for DualNumber {
+impl<T: Scalar> std::ops::DivAssign for DualNumber<T> {
     fn div_assign(&mut self, rhs: Self) {
         *self = *self / rhs;
     }
 }
 
-
-impl std::ops::Add for DualNumber {
-    type Output = DualNumber;
+impl<T: Scalar> std::ops::Add for DualNumber<T> {
+    type Output = DualNumber<T>;
 
     fn add(self, rhs: Self) -> Self::Output {
         DualNumber {
             a: self.a + rhs.a,
-            b: self.b + rhs.b
+            b: self.b + rhs.b,
         }
     }
 }
 
-impl std::ops::Mul for DualNumber {
-    type Output = DualNumber;
+impl<T: Scalar> std::ops::Mul for DualNumber<T> {
+    type Output = DualNumber<T>;
 
     fn mul(self, rhs: Self) -> Self::Output {
         //(a+bE)(c+dE) = ac + bdE^2 + adE + bcE = ac + (ad + bc)E
         DualNumber {
             a: self.a * rhs.a,
-            b: (self.a * rhs.b) + (self.b * rhs.a)
+            b: (self.a * rhs.b) + (self.b * rhs.a),
         }
     }
 }
 
-impl std::ops::Sub for DualNumber {
-    type Output = DualNumber;
+impl<T: Scalar> std::ops::Sub for DualNumber<T> {
+    type Output = DualNumber<T>;
 
     fn sub(self, rhs: Self) -> Self::Output {
         DualNumber {
             a: self.a - rhs.a,
-            b: self.b - rhs.b
+            b: self.b - rhs.b,
         }
     }
 }
 
-impl std::ops::Div for DualNumber {
-    type Output = DualNumber;
+impl<T: Scalar> std::ops::Div for DualNumber<T> {
+    type Output = DualNumber<T>;
 
     fn div(self, rhs: Self) -> Self::Output {
         DualNumber {
             a: self.a / rhs.a,
-            b: (self.b * rhs.a - self.a * rhs.b) / (rhs.a * rhs.a)
+            b: (self.b * rhs.a - self.a * rhs.b) / (rhs.a * rhs.a),
+        }
+    }
+}
+
+impl<T: Scalar> std::ops::Neg for DualNumber<T> {
+    type Output = DualNumber<T>;
+
+    fn neg(self) -> Self::Output {
+        DualNumber {
+            a: -self.a,
+            b: -self.b,
         }
     }
 }
 
+impl<T: Scalar> std::ops::Rem for DualNumber<T> {
+    type Output = DualNumber<T>;
+
+    // x % y = x - trunc(x/y)*y, and trunc(x/y) is piecewise-constant in both
+    // x and y, so d(x % y) = dx - trunc(x/y)*dy. Recover trunc(x/y) as
+    // (x - x%y)/y from the real part we just computed, rather than requiring
+    // Scalar to expose a separate trunc().
+    fn rem(self, rhs: Self) -> Self::Output {
+        let real = self.a % rhs.a;
+        let trunc_quotient = (self.a - real) / rhs.a;
+        DualNumber {
+            a: real,
+            b: self.b - trunc_quotient * rhs.b,
+        }
+    }
+}
+
+impl<T: Scalar> num_traits::Zero for DualNumber<T> {
+    fn zero() -> Self {
+        DualNumber::from_real(T::from_f64(0.0))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.a == T::from_f64(0.0) && self.b == T::from_f64(0.0)
+    }
+}
+
+impl<T: Scalar> num_traits::One for DualNumber<T> {
+    fn one() -> Self {
+        DualNumber::from_real(T::from_f64(1.0))
+    }
+}
+
+impl<T: Scalar> num_traits::MulAdd for DualNumber<T> {
+    type Output = DualNumber<T>;
+
+    fn mul_add(self, a: Self, b: Self) -> Self::Output {
+        DualNumber {
+            a: self.a * a.a + b.a,
+            b: self.a * a.b + self.b * a.a + b.b,
+        }
+    }
+}
+
+impl<T: Scalar> num_traits::MulAddAssign for DualNumber<T> {
+    fn mul_add_assign(&mut self, a: Self, b: Self) {
+        *self = num_traits::MulAdd::mul_add(*self, a, b);
+    }
+}
+
+impl<T: Scalar> num_traits::Num for DualNumber<T> {
+    type FromStrRadixErr = &'static str;
+
+    fn from_str_radix(_str: &str, _radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        Err("DualNumber does not support parsing from strings")
+    }
+}
+
+/// Computes `f'(x)` via forward-mode automatic differentiation.
+///
+/// Seeds `x` as `x + 1*epsilon`, evaluates `f`, and reads off the dual part
+/// of the result.
+///
+/// ```
+/// # use dual_numbers::{differentiate, DualNumber};
+/// let slope = differentiate(4.0, |x: DualNumber| x * x);
+/// assert!((slope - 8.0).abs() < 1e-9);
+/// ```
+pub fn differentiate(x: f64, f: impl Fn(DualNumber) -> DualNumber) -> f64 {
+    f(DualNumber::new(x, 1.0)).dual()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -114,7 +505,7 @@ mod tests {
         let a = DualNumber::new(2., 0.);
         let b = DualNumber::new(3., 0.);
         let c = DualNumber::new(4., 1.);
-        //dc = 1 + 6c^5 = 6145 
+        //dc = 1 + 6c^5 = 6145
         let result = a*b + c + c.powi(6); //4106
         assert_eq!(result, DualNumber::new(4106., 6145.));
     }
@@ -127,4 +518,181 @@ mod tests {
         assert!((y.a - 11.652) < 0.0001);
         assert!(y.b - 5.5 < 0.0001)
     }
+
+    #[test]
+    fn log_with_non_natural_base() {
+        // d/dx log2(x) at x=8 is 1/(8*ln(2)).
+        let x = DualNumber::new(8.0, 1.0);
+        let y = x.log(2.0);
+        assert!((y.a - 3.0).abs() < 1e-9);
+        assert!((y.b - 1.0 / (8.0 * 2.0_f64.ln())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn works_with_f32() {
+        let a: DualNumber<f32> = DualNumber::new(2.0, 1.0);
+        let b: DualNumber<f32> = DualNumber::new(3.0, 0.0);
+        let y = a * b;
+        assert_eq!(y, DualNumber::new(6.0_f32, 3.0_f32));
+    }
+
+    #[test]
+    fn differentiate_polynomial() {
+        let slope = differentiate(3.0, |x| x * x + x);
+        assert!((slope - 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sqrt_derivative() {
+        let slope = differentiate(4.0, |x| x.sqrt());
+        assert!((slope - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cos_derivative_sign() {
+        // f(x) = cos(x), f'(x) = -sin(x); pins the sign that was once missing.
+        let slope = differentiate(1.0, |x| x.cos());
+        assert!((slope - -1.0_f64.sin()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tan_and_atan_are_inverses() {
+        let x = DualNumber::new(0.5, 1.0);
+        let y = x.tan().atan();
+        assert!((y.a - 0.5).abs() < 1e-9);
+        assert!((y.b - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn asin_derivative() {
+        // d/dx asin(x) = 1/sqrt(1-x^2)
+        let slope = differentiate(0.5, |x| x.asin());
+        assert!((slope - 1.0 / (1.0 - 0.5 * 0.5_f64).sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn acos_derivative() {
+        // d/dx acos(x) = -1/sqrt(1-x^2)
+        let slope = differentiate(0.5, |x| x.acos());
+        assert!((slope - -1.0 / (1.0 - 0.5 * 0.5_f64).sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cbrt_derivative() {
+        // d/dx cbrt(x) = 1/(3*cbrt(x)^2)
+        let slope = differentiate(8.0, |x| x.cbrt());
+        assert!((slope - 1.0 / (3.0 * 8.0_f64.cbrt().powi(2))).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ln_derivative() {
+        // d/dx ln(x) = 1/x
+        let slope = differentiate(5.0, |x| x.ln());
+        assert!((slope - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn log2_derivative() {
+        // d/dx log2(x) = 1/(x*ln2)
+        let slope = differentiate(8.0, |x| x.log2());
+        assert!((slope - 1.0 / (8.0 * 2.0_f64.ln())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn log10_derivative() {
+        // d/dx log10(x) = 1/(x*ln10)
+        let slope = differentiate(100.0, |x| x.log10());
+        assert!((slope - 1.0 / (100.0 * 10.0_f64.ln())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn exp2_derivative() {
+        // d/dx 2^x = 2^x*ln2
+        let slope = differentiate(3.0, |x| x.exp2());
+        assert!((slope - 2.0_f64.powi(3) * 2.0_f64.ln()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn abs_derivative() {
+        // d/dx |x| = sign(x)
+        let slope = differentiate(-3.0, |x| x.abs());
+        assert!((slope - -1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn powf_derivative() {
+        // d/dx x^n = n*x^(n-1)
+        let slope = differentiate(2.0, |x| x.powf(3.5));
+        assert!((slope - 3.5 * 2.0_f64.powf(2.5)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn mul_add_matches_mul_then_add() {
+        use num_traits::MulAdd;
+        let x = DualNumber::new(2.0, 1.0);
+        let m = DualNumber::new(3.0, 0.0);
+        let c = DualNumber::new(5.0, 0.0);
+        assert_eq!(x.mul_add(m, c), x * m + c);
+    }
+
+    #[test]
+    fn powi_handles_zero_and_negative_exponents() {
+        let x = DualNumber::new(2.0, 1.0);
+        assert_eq!(x.powi(0), DualNumber::new(1.0, 0.0));
+        let inv = x.powi(-1);
+        assert!((inv.a - 0.5).abs() < 1e-9);
+        assert!((inv.b - -0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn powi_handles_i64_min_without_overflow() {
+        // Negating i64::MIN directly as an i64 has no valid representation.
+        let one = DualNumber::new(1.0, 0.0);
+        assert_eq!(one.powi(i64::MIN), DualNumber::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn pow_with_dual_exponent_matches_x_to_the_x() {
+        // f(x) = x^x, f'(x) = x^x * (ln(x) + 1); at x = 2, f' = 4*(ln2 + 1)
+        let x = DualNumber::new(2.0, 1.0);
+        let y = x.pow(x);
+        assert!((y.a - 4.0).abs() < 1e-9);
+        assert!((y.b - 4.0 * (2.0_f64.ln() + 1.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hypot_matches_sqrt_of_squares() {
+        let x = DualNumber::new(3.0, 1.0);
+        let y = DualNumber::new(4.0, 0.0);
+        let h = x.hypot(y);
+        assert!((h.a - 5.0).abs() < 1e-9);
+        assert!((h.b - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rem_with_constant_modulus() {
+        let x = DualNumber::new(10.0, 1.0);
+        let c = DualNumber::new(3.0, 0.0);
+        let y = x % c;
+        assert_eq!(y, DualNumber::new(1.0, 1.0));
+    }
+
+    #[test]
+    fn rem_with_varying_modulus() {
+        // x % y = x - trunc(x/y)*y, so d(x % y) = dx - trunc(x/y)*dy.
+        let x = DualNumber::new(10.0, 1.0);
+        let y = DualNumber::new(3.0, 0.5);
+        let r = x % y;
+        assert_eq!(r.a, 1.0);
+        assert!((r.b - -0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zero_and_one() {
+        use num_traits::{One, Zero};
+        let z = DualNumber::<f64>::zero();
+        let o = DualNumber::<f64>::one();
+        assert!(z.is_zero());
+        assert_eq!(o, DualNumber::new(1.0, 0.0));
+    }
 }
@@ -0,0 +1,155 @@
+/// A dual number with an `N`-element dual part, used to compute the full
+/// gradient of a multivariable function in a single forward pass.
+///
+/// `b[i]` tracks the partial derivative of the represented value with
+/// respect to input variable `i`. The ordinary [`DualNumber`](crate::DualNumber)
+/// is the `N = 1` case of this idea.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct MultiDual<const N: usize> {
+    a: f64,
+    b: [f64; N],
+}
+
+impl<const N: usize> MultiDual<N> {
+    pub fn new(a: f64, b: [f64; N]) -> Self {
+        MultiDual { a, b }
+    }
+
+    /// A constant: zero in every partial derivative.
+    pub fn from_real(a: f64) -> Self {
+        MultiDual::new(a, [0.0; N])
+    }
+
+    /// Seeds `value` as the `index`-th independent variable: its dual part
+    /// is the unit vector with a `1.0` at `index` and `0.0` elsewhere.
+    pub fn variable(value: f64, index: usize) -> Self {
+        let mut b = [0.0; N];
+        b[index] = 1.0;
+        MultiDual::new(value, b)
+    }
+
+    pub fn real(self) -> f64 {
+        self.a
+    }
+
+    pub fn dual(self) -> [f64; N] {
+        self.b
+    }
+
+    pub fn log(self, base: f64) -> Self {
+        let factor = 1.0 / (self.a * base.ln());
+        MultiDual::new(self.a.log(base), self.b.map(|bi| factor * bi))
+    }
+
+    pub fn exp(self) -> Self {
+        let fa = self.a.exp();
+        MultiDual::new(fa, self.b.map(|bi| fa * bi))
+    }
+
+    pub fn sin(self) -> Self {
+        let cos_a = self.a.cos();
+        MultiDual::new(self.a.sin(), self.b.map(|bi| cos_a * bi))
+    }
+
+    pub fn cos(self) -> Self {
+        let neg_sin_a = -self.a.sin();
+        MultiDual::new(self.a.cos(), self.b.map(|bi| neg_sin_a * bi))
+    }
+}
+
+impl<const N: usize> std::ops::Add for MultiDual<N> {
+    type Output = MultiDual<N>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut b = self.b;
+        for (bi, rhs_bi) in b.iter_mut().zip(rhs.b) {
+            *bi += rhs_bi;
+        }
+        MultiDual::new(self.a + rhs.a, b)
+    }
+}
+
+impl<const N: usize> std::ops::Sub for MultiDual<N> {
+    type Output = MultiDual<N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut b = self.b;
+        for (bi, rhs_bi) in b.iter_mut().zip(rhs.b) {
+            *bi -= rhs_bi;
+        }
+        MultiDual::new(self.a - rhs.a, b)
+    }
+}
+
+impl<const N: usize> std::ops::Mul for MultiDual<N> {
+    type Output = MultiDual<N>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut b = [0.0; N];
+        for (bi, (self_bi, rhs_bi)) in b.iter_mut().zip(self.b.into_iter().zip(rhs.b)) {
+            *bi = self.a * rhs_bi + self_bi * rhs.a;
+        }
+        MultiDual::new(self.a * rhs.a, b)
+    }
+}
+
+impl<const N: usize> std::ops::Div for MultiDual<N> {
+    type Output = MultiDual<N>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let mut b = [0.0; N];
+        for (bi, (self_bi, rhs_bi)) in b.iter_mut().zip(self.b.into_iter().zip(rhs.b)) {
+            *bi = (self_bi * rhs.a - self.a * rhs_bi) / (rhs.a * rhs.a);
+        }
+        MultiDual::new(self.a / rhs.a, b)
+    }
+}
+
+impl<const N: usize> std::ops::Neg for MultiDual<N> {
+    type Output = MultiDual<N>;
+
+    fn neg(self) -> Self::Output {
+        MultiDual::new(-self.a, self.b.map(|bi| -bi))
+    }
+}
+
+/// Computes the full gradient of `f` at `point` in one forward pass.
+///
+/// Each coordinate of `point` is seeded as an independent [`MultiDual`]
+/// variable, `f` is evaluated once, and the resulting dual part *is* the
+/// gradient.
+pub fn gradient<const N: usize>(f: impl Fn([MultiDual<N>; N]) -> MultiDual<N>, point: [f64; N]) -> [f64; N] {
+    let vars = std::array::from_fn(|i| MultiDual::variable(point[i], i));
+    f(vars).dual()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_of_xy_plus_x_squared() {
+        // f(x, y) = x*y + x^2  =>  df/dx = y + 2x, df/dy = x
+        let grad = gradient(|v: [MultiDual<2>; 2]| v[0] * v[1] + v[0] * v[0], [3.0, 4.0]);
+        assert!((grad[0] - 10.0).abs() < 1e-9);
+        assert!((grad[1] - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn componentwise_addition() {
+        let a = MultiDual::variable(2.0, 0);
+        let b = MultiDual::variable(5.0, 1);
+        let y = a + b;
+        assert_eq!(y, MultiDual::new(7.0, [1.0, 1.0]));
+    }
+
+    #[test]
+    fn log_agrees_with_dual_number() {
+        use crate::DualNumber;
+
+        let single = DualNumber::new(8.0, 1.0).log(2.0);
+        let multi = MultiDual::<1>::variable(8.0, 0).log(2.0);
+        assert!((single.real() - multi.real()).abs() < 1e-9);
+        assert!((single.dual() - multi.dual()[0]).abs() < 1e-9);
+    }
+}